@@ -1,10 +1,35 @@
+use arc_swap::ArcSwap;
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
 use miette::{set_panic_hook, Context, IntoDiagnostic, Result};
+use std::sync::Arc;
 use tokio_stream::StreamExt;
+use vetta_core::config::{spawn_config_watcher, Config};
 use vetta_core::domain::Quarter as CoreQuarter;
+use vetta_core::earnings_processor::transcode::normalize_to_wav;
 use vetta_core::earnings_processor::validate_media_file;
-use vetta_core::stt::{LocalSttStrategy, SpeechToText, TranscribeOptions};
+use vetta_core::stt::{
+    chunked, ChunkedTranscriber, LocalSttStrategy, RemoteSttStrategy, SpeechToText, SttBackend,
+    TranscribeOptions,
+};
+
+const CONFIG_PATH: &str = "config.toml";
+
+#[derive(Debug, Clone, Default, ValueEnum)]
+enum CliBackend {
+    #[default]
+    Local,
+    Remote,
+}
+
+impl From<CliBackend> for SttBackend {
+    fn from(cli: CliBackend) -> Self {
+        match cli {
+            CliBackend::Local => SttBackend::Local,
+            CliBackend::Remote => SttBackend::Remote,
+        }
+    }
+}
 
 #[derive(Debug, Clone, ValueEnum)]
 enum CliQuarter {
@@ -54,6 +79,19 @@ enum EarningsAction {
         year: u16,
         #[arg(short, long, value_enum)]
         quarter: CliQuarter,
+        #[arg(short, long, value_enum, default_value = "local")]
+        backend: CliBackend,
+        #[arg(
+            long,
+            help = "Split long input into overlapping windows transcribed concurrently"
+        )]
+        chunked: bool,
+        #[arg(long, default_value_t = chunked::DEFAULT_WINDOW_SECS)]
+        chunk_window_secs: f32,
+        #[arg(long, default_value_t = chunked::DEFAULT_OVERLAP_SECS)]
+        chunk_overlap_secs: f32,
+        #[arg(long, default_value_t = chunked::DEFAULT_CONCURRENCY)]
+        chunk_concurrency: usize,
     },
 }
 
@@ -63,6 +101,22 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    let config = Arc::new(ArcSwap::from_pointee(
+        Config::from_file(CONFIG_PATH)
+            .into_diagnostic()
+            .wrap_err("Failed to load config.toml")?,
+    ));
+    // Kept alive for the process lifetime so edits to config.toml take effect
+    // without a restart; dropping it would stop the watch. Not having a
+    // config.toml to watch is fine — we just run with defaults.
+    let _config_watcher = if std::path::Path::new(CONFIG_PATH).exists() {
+        spawn_config_watcher(CONFIG_PATH, config.clone())
+            .inspect_err(|err| eprintln!("config: failed to start watcher: {err}"))
+            .ok()
+    } else {
+        None
+    };
+
     match cli.command {
         Resource::Earnings { action } => match action {
             EarningsAction::Process {
@@ -70,6 +124,11 @@ async fn main() -> Result<()> {
                 ticker,
                 year,
                 quarter,
+                backend,
+                chunked,
+                chunk_window_secs,
+                chunk_overlap_secs,
+                chunk_concurrency,
             } => {
                 let core_quarter: CoreQuarter = quarter.into();
 
@@ -86,10 +145,22 @@ async fn main() -> Result<()> {
                 println!();
 
                 // ── 1. Validation ──────────────────────────────────────────
-                let file_info = validate_media_file(&file).wrap_err("Validation phase failed")?;
+                let audio_meta = validate_media_file(&file, &config.load())
+                    .wrap_err("Validation phase failed")?;
 
                 println!("   {}", "✔ VALIDATION PASSED".green().bold());
-                println!("   {:<10} {}", "Format:".dimmed(), file_info);
+                println!(
+                    "   {:<10} {} ({}MB)",
+                    "Format:".dimmed(),
+                    audio_meta.mime,
+                    audio_meta.size_mb
+                );
+                if let Some(duration_secs) = audio_meta.duration_secs {
+                    println!("   {:<10} {:.1}s", "Duration:".dimmed(), duration_secs);
+                }
+                if let Some(warning) = audio_meta.sample_rate_warning() {
+                    println!("   {} {}", "⚠".yellow(), warning.to_string().yellow());
+                }
                 println!();
 
                 println!("   {}", "Processing Pipeline:".bold().blue());
@@ -97,26 +168,73 @@ async fn main() -> Result<()> {
                 println!("   2. [{}] Transcription (Whisper)", "RUNNING".yellow());
 
                 // ── 2. Transcription ───────────────────────────────────────
-                let stt = LocalSttStrategy::connect("/tmp/whisper.sock")
-                    .await
-                    .into_diagnostic()
-                    .wrap_err(
-                        "Failed to connect to STT service — is the whisper service running?",
-                    )?;
+                let cfg = config.load();
 
-                let options = TranscribeOptions {
-                    language: Some("en".into()),
-                    initial_prompt: Some(
-                        "Earnings call transcript. Financial terminology, \
-                         company names, analyst questions and management responses."
-                            .into(),
+                let base_stt: Arc<dyn SpeechToText> = match SttBackend::from(backend) {
+                    SttBackend::Local => Arc::new(
+                        LocalSttStrategy::connect(cfg.socket_path.clone())
+                            .await
+                            .into_diagnostic()
+                            .wrap_err(
+                                "Failed to connect to STT service — is the whisper service running?",
+                            )?,
                     ),
-                    diarization: false,
-                    num_speakers: 2,
+                    SttBackend::Remote => {
+                        let api_key = std::env::var("VETTA_CLOUD_STT_API_KEY").unwrap_or_default();
+                        Arc::new(
+                            RemoteSttStrategy::connect(
+                                "https://stt.vetta-cloud.example.com:443",
+                                api_key,
+                            )
+                            .await
+                            .into_diagnostic()
+                            .wrap_err("Failed to connect to cloud STT service")?,
+                        )
+                    }
+                };
+
+                let stt: Arc<dyn SpeechToText> = if chunked {
+                    println!(
+                        "   {:<10} {}s windows, {}s overlap, {} concurrent",
+                        "Chunked:".dimmed(),
+                        chunk_window_secs,
+                        chunk_overlap_secs,
+                        chunk_concurrency
+                    );
+                    Arc::new(ChunkedTranscriber::new(base_stt))
+                } else {
+                    base_stt
+                };
+
+                let options = TranscribeOptions {
+                    language: Some(cfg.language.clone()),
+                    initial_prompt: Some(cfg.initial_prompt.clone()),
+                    diarization: cfg.diarization,
+                    num_speakers: cfg.num_speakers,
+                    chunk_window_secs,
+                    chunk_overlap_secs,
+                    chunk_concurrency,
+                };
+
+                // ── Normalization ───────────────────────────────────────────
+                // Kept alive until transcription finishes below — `normalized.path`
+                // is a `TempPath` that deletes the file on drop.
+                let mut _normalized_guard = None;
+                let transcribe_path = if cfg.normalize_audio {
+                    let normalized = normalize_to_wav(&file)
+                        .await
+                        .into_diagnostic()
+                        .wrap_err("Audio normalization failed")?;
+                    println!("   {:<10} 16kHz mono WAV", "Normalized:".dimmed());
+                    let path = normalized.path.to_string_lossy().into_owned();
+                    _normalized_guard = Some(normalized);
+                    path
+                } else {
+                    file.clone()
                 };
 
                 let mut stream = stt
-                    .transcribe(&file, options)
+                    .transcribe(&transcribe_path, options)
                     .await
                     .into_diagnostic()
                     .wrap_err("Transcription failed")?;
@@ -128,15 +246,19 @@ async fn main() -> Result<()> {
                         .into_diagnostic()
                         .wrap_err("Error reading transcript chunk")?;
 
-                    segment_count += 1;
-
-                    // Live progress — overwrite the same line
+                    // Interim results overwrite the same line; a finalized chunk
+                    // keeps its line and counts toward the segment total.
                     print!(
                         "\r   [{:.1}s → {:.1}s] {}",
                         chunk.start_time,
                         chunk.end_time,
                         chunk.text.trim()
                     );
+
+                    if !chunk.is_partial {
+                        segment_count += 1;
+                        println!();
+                    }
                 }
 
                 // Clear progress line then print final status
@@ -0,0 +1,178 @@
+use arc_swap::ArcSwap;
+use miette::Diagnostic;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use thiserror::Error;
+
+const DEFAULT_SOCKET_PATH: &str = "/tmp/whisper.sock";
+const DEFAULT_LANGUAGE: &str = "en";
+const DEFAULT_INITIAL_PROMPT: &str = "Earnings call transcript. Financial terminology, \
+     company names, analyst questions and management responses.";
+const DEFAULT_MAX_FILE_SIZE_MB: u64 = 500;
+const DEFAULT_ALLOWED_MIME_TYPES: [&str; 5] = [
+    "audio/mpeg",
+    "audio/wav",
+    "audio/x-wav",
+    "audio/x-m4a",
+    "video/mp4",
+];
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ConfigError {
+    #[error(transparent)]
+    #[diagnostic(code(vetta::config::io))]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse config.toml: {0}")]
+    #[diagnostic(
+        code(vetta::config::parse),
+        help(
+            "Check config.toml against the documented fields: socket_path, language, \
+             initial_prompt, diarization, num_speakers, max_file_size_mb, allowed_mime_types, \
+             normalize_audio"
+        )
+    )]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Runtime configuration for the ingest/transcription pipeline, loaded from
+/// `config.toml`. Any field missing from the file falls back to its default.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub socket_path: String,
+    pub language: String,
+    pub initial_prompt: String,
+    pub diarization: bool,
+    pub num_speakers: u32,
+    pub max_file_size_mb: u64,
+    pub allowed_mime_types: Vec<String>,
+    /// Run `earnings_processor::transcode` on the input before handing it to an
+    /// `SpeechToText` strategy, so diarization and word timing always line up
+    /// against a known 16kHz mono sample rate. Requires `ffmpeg` on `PATH`.
+    pub normalize_audio: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            socket_path: DEFAULT_SOCKET_PATH.to_string(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            initial_prompt: DEFAULT_INITIAL_PROMPT.to_string(),
+            diarization: false,
+            num_speakers: 2,
+            max_file_size_mb: DEFAULT_MAX_FILE_SIZE_MB,
+            allowed_mime_types: DEFAULT_ALLOWED_MIME_TYPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            normalize_audio: false,
+        }
+    }
+}
+
+impl Config {
+    /// Loads `Config` from the TOML file at `path`. A missing file is not an
+    /// error — it yields `Config::default()`, since vetta runs fine unconfigured.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the file exists but can't be read, or
+    /// `ConfigError::Parse` if it exists but isn't valid TOML for this shape.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+}
+
+/// Watches `path` in the background and swaps the live `Config` behind `live`
+/// whenever the file changes, so a long-running process picks up edits without
+/// a restart. The returned `RecommendedWatcher` must be kept alive for as long
+/// as the watch should run — dropping it stops the watch.
+///
+/// Reload failures are logged to stderr and leave the previously loaded config
+/// in place.
+pub fn spawn_config_watcher(
+    path: impl Into<PathBuf>,
+    live: Arc<ArcSwap<Config>>,
+) -> notify::Result<RecommendedWatcher> {
+    let path = path.into();
+    let (tx, rx) = channel();
+
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&path, RecursiveMode::NonRecursive)?;
+
+    std::thread::spawn(move || {
+        for event in rx {
+            let should_reload = matches!(
+                event,
+                Ok(ref e) if e.kind.is_modify() || e.kind.is_create()
+            );
+            if !should_reload {
+                continue;
+            }
+
+            match Config::from_file(&path) {
+                Ok(new_config) => live.store(Arc::new(new_config)),
+                Err(err) => eprintln!("config: failed to reload {}: {err}", path.display()),
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_from_file_missing_path_yields_default() {
+        let config = Config::from_file("/nonexistent/path/config.toml").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_from_file_parses_valid_toml() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"
+            socket_path = "/tmp/custom.sock"
+            language = "fr"
+            diarization = true
+            num_speakers = 3
+            "#
+        )
+        .unwrap();
+
+        let config = Config::from_file(file.path()).unwrap();
+
+        assert_eq!(config.socket_path, "/tmp/custom.sock");
+        assert_eq!(config.language, "fr");
+        assert!(config.diarization);
+        assert_eq!(config.num_speakers, 3);
+        // Fields absent from the file fall back to their defaults.
+        assert_eq!(config.max_file_size_mb, Config::default().max_file_size_mb);
+    }
+
+    #[test]
+    fn test_from_file_invalid_toml_returns_parse_error() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "this is not valid toml : : :").unwrap();
+
+        let err = Config::from_file(file.path()).unwrap_err();
+
+        assert!(matches!(err, ConfigError::Parse(_)));
+    }
+}
@@ -1,16 +1,15 @@
+pub mod transcode;
+
+use crate::config::Config;
 use miette::Diagnostic;
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
 
-const MAX_FILE_SIZE_MB: u64 = 500;
-const ALLOWED_MIME_TYPES: [&str; 5] = [
-    "audio/mpeg",  // .mp3
-    "audio/wav",   // .wav
-    "audio/x-wav", // .wav
-    "audio/x-m4a", // .m4a
-    "video/mp4",   // .mp4
-];
+/// Sample rate the whisper pipeline is tuned for; anything else still transcribes
+/// but may lose accuracy until it's resampled.
+const EXPECTED_SAMPLE_RATE_HZ: u32 = 16_000;
+const EXPECTED_CHANNELS: u16 = 1;
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum IngestError {
@@ -56,9 +55,172 @@ pub enum IngestError {
     #[error(transparent)]
     #[diagnostic(code(vetta::io::error))]
     Io(#[from] std::io::Error),
+
+    #[error("Truncated WAV header: {0}")]
+    #[diagnostic(
+        code(vetta::ingest::truncated_header),
+        help("The file is shorter than its RIFF header claims. The download or export likely got cut off.")
+    )]
+    TruncatedHeader(String),
+
+    #[error("Corrupt WAV header: {0}")]
+    #[diagnostic(
+        code(vetta::ingest::corrupt_header),
+        help("The RIFF/fmt/data chunks are malformed. Try re-exporting or converting the file with ffmpeg.")
+    )]
+    CorruptHeader(String),
+
+    #[error("Sample rate {sample_rate}Hz / {channels}ch audio may not transcribe well")]
+    #[diagnostic(
+        code(vetta::ingest::suboptimal_sample_rate),
+        help(
+            "The whisper pipeline is tuned for {EXPECTED_SAMPLE_RATE_HZ}Hz mono audio. Consider resampling with ffmpeg for best results."
+        )
+    )]
+    SuboptimalSampleRate { sample_rate: u32, channels: u16 },
+
+    #[error("ffmpeg transcoding failed")]
+    #[diagnostic(
+        code(vetta::ingest::transcode_failed),
+        help("ffmpeg reported:\n{stderr}")
+    )]
+    TranscodeFailed { stderr: String },
+}
+
+/// Structured audio metadata sniffed from the container. Fields that can only be
+/// read from a container we know how to parse (currently WAV) are `None` for
+/// other accepted formats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioMetadata {
+    pub mime: String,
+    pub size_mb: u64,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+    pub bits_per_sample: Option<u16>,
+    pub duration_secs: Option<f64>,
+}
+
+impl AudioMetadata {
+    /// Returns a `SuboptimalSampleRate` diagnostic if the known sample rate/channel
+    /// count isn't what the whisper pipeline is tuned for. This is informational —
+    /// callers should warn, not fail, the pipeline on it.
+    pub fn sample_rate_warning(&self) -> Option<IngestError> {
+        let sample_rate = self.sample_rate?;
+        let channels = self.channels?;
+        if sample_rate != EXPECTED_SAMPLE_RATE_HZ || channels != EXPECTED_CHANNELS {
+            Some(IngestError::SuboptimalSampleRate {
+                sample_rate,
+                channels,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+struct WavFmt {
+    num_channels: u16,
+    sample_rate: u32,
+    byte_rate: u32,
+    bits_per_sample: u16,
+}
+
+/// Walks a WAV/RIFF file's subchunks to pull out the `fmt ` and `data` chunks.
+///
+/// Returns `IngestError::TruncatedHeader` if the file ends before a complete
+/// chunk can be read, and `IngestError::CorruptHeader` if the RIFF/WAVE magic or
+/// chunk sizes don't make sense.
+fn parse_wav_metadata(path: &Path) -> Result<(WavFmt, u32), IngestError> {
+    let (fmt, data_size, _data_offset) = parse_wav_chunks(path)?;
+    Ok((fmt, data_size))
+}
+
+/// Locates the `data` chunk of a WAV file produced by
+/// `earnings_processor::transcode`, returning `(offset, len)` of its raw PCM
+/// payload within the file. Used by streaming `SpeechToText` strategies that
+/// need to upload PCM samples without the surrounding RIFF framing.
+pub(crate) fn wav_pcm_span(path: &Path) -> Result<(u64, u32), IngestError> {
+    let (_fmt, data_size, data_offset) = parse_wav_chunks(path)?;
+    Ok((data_offset as u64, data_size))
+}
+
+fn parse_wav_chunks(path: &Path) -> Result<(WavFmt, u32, usize), IngestError> {
+    let bytes = fs::read(path)?;
+    let truncated = || IngestError::TruncatedHeader(path.display().to_string());
+
+    if bytes.len() < 12 {
+        return Err(truncated());
+    }
+    if &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(IngestError::CorruptHeader(format!(
+            "{} is not a RIFF/WAVE container",
+            path.display()
+        )));
+    }
+
+    let mut fmt: Option<WavFmt> = None;
+    let mut data_size: Option<u32> = None;
+    let mut data_offset: Option<usize> = None;
+    let mut cursor = &bytes[12..];
+
+    while fmt.is_none() || data_size.is_none() {
+        if cursor.len() < 8 {
+            if !cursor.is_empty() {
+                return Err(truncated());
+            }
+            break;
+        }
+        let chunk_id = &cursor[0..4];
+        let chunk_size = u32::from_le_bytes(cursor[4..8].try_into().unwrap());
+        let body_end = 8usize
+            .checked_add(chunk_size as usize)
+            .ok_or_else(|| IngestError::CorruptHeader("chunk size overflow".to_string()))?;
+        if cursor.len() < body_end {
+            return Err(truncated());
+        }
+        let body = &cursor[8..body_end];
+
+        match chunk_id {
+            b"fmt " => {
+                if body.len() < 16 {
+                    return Err(IngestError::CorruptHeader(
+                        "fmt chunk shorter than 16 bytes".to_string(),
+                    ));
+                }
+                fmt = Some(WavFmt {
+                    num_channels: u16::from_le_bytes(body[2..4].try_into().unwrap()),
+                    sample_rate: u32::from_le_bytes(body[4..8].try_into().unwrap()),
+                    byte_rate: u32::from_le_bytes(body[8..12].try_into().unwrap()),
+                    bits_per_sample: u16::from_le_bytes(body[14..16].try_into().unwrap()),
+                });
+            }
+            b"data" => {
+                data_size = Some(chunk_size);
+                data_offset = Some(bytes.len() - cursor.len() + 8);
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even number of bytes.
+        let advance = body_end + (chunk_size as usize % 2);
+        if advance >= cursor.len() {
+            break;
+        }
+        cursor = &cursor[advance..];
+    }
+
+    let fmt = fmt.ok_or_else(|| {
+        IngestError::CorruptHeader(format!("{} has no fmt chunk", path.display()))
+    })?;
+    let data_size = data_size.ok_or_else(|| {
+        IngestError::CorruptHeader(format!("{} has no data chunk", path.display()))
+    })?;
+    let data_offset = data_offset.expect("data_offset set alongside data_size");
+
+    Ok((fmt, data_size, data_offset))
 }
 
-pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
+pub fn validate_media_file(path_str: &str, config: &Config) -> Result<AudioMetadata, IngestError> {
     let path = Path::new(path_str);
 
     if !path.exists() {
@@ -71,9 +233,9 @@ pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
     }
 
     let size_mb = metadata.len() / (1024 * 1024);
-    if size_mb > MAX_FILE_SIZE_MB {
+    if size_mb > config.max_file_size_mb {
         return Err(IngestError::FileTooLarge {
-            limit: MAX_FILE_SIZE_MB,
+            limit: config.max_file_size_mb,
             got: size_mb,
         });
     }
@@ -82,11 +244,40 @@ pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
         .map_err(IngestError::Io)?
         .ok_or(IngestError::UnknownType)?;
 
-    if !ALLOWED_MIME_TYPES.contains(&kind.mime_type()) {
+    if !config
+        .allowed_mime_types
+        .iter()
+        .any(|mime| mime == kind.mime_type())
+    {
         return Err(IngestError::InvalidFormat(kind.mime_type().to_string()));
     }
 
-    Ok(format!("{} ({}MB)", kind.mime_type(), size_mb))
+    let (sample_rate, channels, bits_per_sample, duration_secs) =
+        if kind.mime_type() == "audio/wav" || kind.mime_type() == "audio/x-wav" {
+            let (fmt, data_size) = parse_wav_metadata(path)?;
+            let duration_secs = if fmt.byte_rate > 0 {
+                Some(data_size as f64 / fmt.byte_rate as f64)
+            } else {
+                None
+            };
+            (
+                Some(fmt.sample_rate),
+                Some(fmt.num_channels),
+                Some(fmt.bits_per_sample),
+                duration_secs,
+            )
+        } else {
+            (None, None, None, None)
+        };
+
+    Ok(AudioMetadata {
+        mime: kind.mime_type().to_string(),
+        size_mb,
+        sample_rate,
+        channels,
+        bits_per_sample,
+        duration_secs,
+    })
 }
 
 #[cfg(test)]
@@ -97,7 +288,7 @@ mod tests {
 
     #[test]
     fn test_file_not_found() {
-        let result = validate_media_file("non_existent_file.mp3");
+        let result = validate_media_file("non_existent_file.mp3", &Config::default());
         assert!(matches!(result, Err(IngestError::FileNotFound(_))));
     }
 
@@ -105,24 +296,25 @@ mod tests {
     fn test_file_empty() {
         let file = NamedTempFile::new().unwrap();
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &Config::default());
         assert!(matches!(result, Err(IngestError::FileEmpty)));
     }
 
     #[test]
     fn test_file_too_large() {
+        let config = Config::default();
         let mut file = NamedTempFile::new().unwrap();
-        // 501 MB
-        let size = (MAX_FILE_SIZE_MB + 1) * 1024 * 1024;
+        // 1 MB over the configured limit
+        let size = (config.max_file_size_mb + 1) * 1024 * 1024;
         file.as_file_mut().set_len(size).unwrap();
 
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &config);
 
         match result {
             Err(IngestError::FileTooLarge { limit, got }) => {
-                assert_eq!(limit, MAX_FILE_SIZE_MB);
-                assert_eq!(got, MAX_FILE_SIZE_MB + 1);
+                assert_eq!(limit, config.max_file_size_mb);
+                assert_eq!(got, config.max_file_size_mb + 1);
             }
             _ => panic!("Expected FileTooLarge error, got {:?}", result),
         }
@@ -138,7 +330,7 @@ mod tests {
         file.write_all(b"%PDF-1.4\n").unwrap();
 
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &Config::default());
 
         match result {
             Err(IngestError::InvalidFormat(mime)) => {
@@ -155,7 +347,7 @@ mod tests {
         file.write_all(&[0x00, 0x01, 0x02, 0x03, 0x04]).unwrap();
 
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &Config::default());
 
         assert!(matches!(result, Err(IngestError::UnknownType)));
     }
@@ -168,28 +360,134 @@ mod tests {
             .unwrap();
 
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &Config::default());
+
+        match result {
+            Ok(meta) => assert_eq!(meta.mime, "audio/mpeg"),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    /// Builds a minimal but complete 16-bit PCM WAV file: RIFF/WAVE header,
+    /// `fmt ` chunk, then a `data` chunk containing `num_samples` zeroed samples.
+    fn write_wav(
+        file: &mut NamedTempFile,
+        sample_rate: u32,
+        num_channels: u16,
+        bits_per_sample: u16,
+        num_samples: usize,
+    ) {
+        let block_align = num_channels * (bits_per_sample / 8);
+        let byte_rate = sample_rate * block_align as u32;
+        let data = vec![0u8; num_samples * block_align as usize];
+
+        let mut fmt_chunk = Vec::new();
+        fmt_chunk.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        fmt_chunk.extend_from_slice(&num_channels.to_le_bytes());
+        fmt_chunk.extend_from_slice(&sample_rate.to_le_bytes());
+        fmt_chunk.extend_from_slice(&byte_rate.to_le_bytes());
+        fmt_chunk.extend_from_slice(&block_align.to_le_bytes());
+        fmt_chunk.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+        let riff_size = 4 + (8 + fmt_chunk.len()) + (8 + data.len());
+
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&(riff_size as u32).to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&(fmt_chunk.len() as u32).to_le_bytes())
+            .unwrap();
+        file.write_all(&fmt_chunk).unwrap();
 
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("audio/mpeg"));
+        file.write_all(b"data").unwrap();
+        file.write_all(&(data.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&data).unwrap();
     }
 
     #[test]
     fn test_valid_wav() {
         let mut file = NamedTempFile::new().unwrap();
-        // WAV magic bytes: RIFF (52 49 46 46) ... WAVE (57 41 56 45)
-        // RIFF + 4 bytes size + WAVE
-        let mut wav_data = vec![0u8; 12];
-        wav_data[0..4].copy_from_slice(b"RIFF");
-        wav_data[8..12].copy_from_slice(b"WAVE");
-        file.write_all(&wav_data).unwrap();
+        write_wav(&mut file, 16_000, 1, 16, 16_000);
 
         let path = file.path().to_str().unwrap();
-        let result = validate_media_file(path);
+        let result = validate_media_file(path, &Config::default());
 
-        match &result {
-            Ok(msg) => assert!(msg.contains("audio/wav") || msg.contains("audio/x-wav")),
+        match result {
+            Ok(meta) => {
+                assert!(meta.mime == "audio/wav" || meta.mime == "audio/x-wav");
+                assert_eq!(meta.sample_rate, Some(16_000));
+                assert_eq!(meta.channels, Some(1));
+                assert_eq!(meta.bits_per_sample, Some(16));
+                assert_eq!(meta.duration_secs, Some(1.0));
+                assert!(meta.sample_rate_warning().is_none());
+            }
             Err(e) => panic!("Expected Ok, got Err: {:?}", e),
         }
     }
+
+    #[test]
+    fn test_wav_suboptimal_sample_rate_warns() {
+        let mut file = NamedTempFile::new().unwrap();
+        write_wav(&mut file, 44_100, 2, 16, 100);
+
+        let path = file.path().to_str().unwrap();
+        let meta = validate_media_file(path, &Config::default()).unwrap();
+
+        match meta.sample_rate_warning() {
+            Some(IngestError::SuboptimalSampleRate {
+                sample_rate,
+                channels,
+            }) => {
+                assert_eq!(sample_rate, 44_100);
+                assert_eq!(channels, 2);
+            }
+            other => panic!("Expected SuboptimalSampleRate warning, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_wav_no_fmt_chunk() {
+        let mut file = NamedTempFile::new().unwrap();
+        // RIFF/WAVE magic with no fmt/data chunks following.
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+
+        let path = file.path().to_str().unwrap();
+        let result = validate_media_file(path, &Config::default());
+
+        assert!(matches!(result, Err(IngestError::CorruptHeader(_))));
+    }
+
+    #[test]
+    fn test_wav_truncated_fmt_chunk() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        // Claims a 16-byte fmt chunk but the file ends after 4 bytes of it.
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&16u32.to_le_bytes()).unwrap();
+        file.write_all(&[0u8; 4]).unwrap();
+
+        let path = file.path().to_str().unwrap();
+        let result = validate_media_file(path, &Config::default());
+
+        assert!(matches!(result, Err(IngestError::TruncatedHeader(_))));
+    }
+
+    #[test]
+    fn test_wav_corrupt_magic() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+        file.write_all(b"JUNK").unwrap();
+
+        // `infer` doesn't recognize RIFF without WAVE, so validate_media_file would
+        // stop at UnknownType; exercise the header parser's magic check directly.
+        let result = parse_wav_metadata(file.path());
+
+        assert!(matches!(result, Err(IngestError::CorruptHeader(_))));
+    }
 }
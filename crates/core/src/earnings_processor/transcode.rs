@@ -0,0 +1,153 @@
+use super::IngestError;
+use std::process::Stdio;
+use tempfile::TempPath;
+use tokio::process::Command;
+
+const TARGET_SAMPLE_RATE_HZ: &str = "16000";
+const TARGET_CHANNELS: &str = "1";
+
+/// The normalized WAV produced by [`normalize_to_wav`] or [`extract_window`], plus
+/// the duration ffmpeg detected in the *source* file when it reported one.
+///
+/// `path` owns the temp file: it's deleted as soon as this value (or whatever
+/// it's moved into) is dropped, so hang onto a `TranscodedAudio` for as long as
+/// you need the file on disk rather than extracting the path early.
+pub struct TranscodedAudio {
+    pub path: TempPath,
+    pub source_duration_secs: Option<f64>,
+}
+
+/// Decodes `input_path` (mp3/m4a/mp4/wav) to 16kHz mono 16-bit PCM WAV in a fresh
+/// temp file via `ffmpeg`, equivalent to an `audioconvert -> resample` pipeline.
+///
+/// # Errors
+///
+/// Returns `IngestError::TranscodeFailed` with ffmpeg's captured stderr if the
+/// process exits non-zero, or `IngestError::Io` if ffmpeg can't be spawned or
+/// the temp file can't be created.
+pub async fn normalize_to_wav(input_path: &str) -> Result<TranscodedAudio, IngestError> {
+    run_ffmpeg_to_wav(input_path, None, None).await
+}
+
+/// Extracts `duration_secs` seconds of audio starting at `start_secs` from
+/// `input_path`, decoded to the same 16kHz mono 16-bit PCM WAV as
+/// [`normalize_to_wav`]. Used by `ChunkedTranscriber` to slice long recordings
+/// into windows.
+pub async fn extract_window(
+    input_path: &str,
+    start_secs: f32,
+    duration_secs: f32,
+) -> Result<TranscodedAudio, IngestError> {
+    run_ffmpeg_to_wav(input_path, Some(start_secs), Some(duration_secs)).await
+}
+
+async fn run_ffmpeg_to_wav(
+    input_path: &str,
+    start_secs: Option<f32>,
+    duration_secs: Option<f32>,
+) -> Result<TranscodedAudio, IngestError> {
+    let temp = tempfile::Builder::new()
+        .prefix("vetta-transcode-")
+        .suffix(".wav")
+        .tempfile()
+        .map_err(IngestError::Io)?;
+    // `into_temp_path` keeps the file around (ffmpeg writes to it by path below)
+    // but, unlike `.keep()`, leaves it owned by a `TempPath` guard that still
+    // deletes it on drop — callers just need to hold onto `TranscodedAudio`.
+    let output_path = temp.into_temp_path();
+    let output_path_str = output_path.to_string_lossy().into_owned();
+
+    let mut args: Vec<String> = vec!["-y".to_string()];
+    if let Some(start_secs) = start_secs {
+        args.push("-ss".to_string());
+        args.push(start_secs.to_string());
+    }
+    if let Some(duration_secs) = duration_secs {
+        args.push("-t".to_string());
+        args.push(duration_secs.to_string());
+    }
+    args.extend([
+        "-i".to_string(),
+        input_path.to_string(),
+        "-ar".to_string(),
+        TARGET_SAMPLE_RATE_HZ.to_string(),
+        "-ac".to_string(),
+        TARGET_CHANNELS.to_string(),
+        "-sample_fmt".to_string(),
+        "s16".to_string(),
+        output_path_str,
+    ]);
+
+    let output = Command::new("ffmpeg")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(IngestError::Io)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if !output.status.success() {
+        return Err(IngestError::TranscodeFailed { stderr });
+    }
+
+    Ok(TranscodedAudio {
+        path: output_path,
+        source_duration_secs: parse_ffmpeg_duration(&stderr),
+    })
+}
+
+/// Probes `input_path` for its total duration without writing any decoded
+/// output, by parsing the `Duration:` line ffmpeg prints for every input.
+///
+/// # Errors
+///
+/// Returns `IngestError::TranscodeFailed` if ffmpeg doesn't report a duration.
+pub(crate) async fn probe_duration_secs(input_path: &str) -> Result<f64, IngestError> {
+    let output = Command::new("ffmpeg")
+        .args(["-i", input_path, "-f", "null", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(IngestError::Io)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    parse_ffmpeg_duration(&stderr).ok_or(IngestError::TranscodeFailed { stderr })
+}
+
+/// Parses the `Duration: HH:MM:SS.ss` line ffmpeg writes to stderr for the input
+/// file, returning the total number of seconds.
+fn parse_ffmpeg_duration(ffmpeg_stderr: &str) -> Option<f64> {
+    let line = ffmpeg_stderr.lines().find_map(|line| {
+        let rest = line.trim_start().strip_prefix("Duration:")?;
+        Some(rest.split(',').next()?.trim().to_string())
+    })?;
+
+    let mut parts = line.splitn(3, ':');
+    let hours: f64 = parts.next()?.trim().parse().ok()?;
+    let minutes: f64 = parts.next()?.trim().parse().ok()?;
+    let seconds: f64 = parts.next()?.trim().parse().ok()?;
+
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffmpeg_duration() {
+        let stderr = "Input #0, wav, from 'in.wav':\n  Duration: 00:01:23.45, bitrate: 256 kb/s\n";
+        assert_eq!(parse_ffmpeg_duration(stderr), Some(83.45));
+    }
+
+    #[test]
+    fn test_parse_ffmpeg_duration_missing() {
+        let stderr = "Input #0, wav, from 'in.wav':\n  Stream #0:0: Audio: pcm_s16le\n";
+        assert_eq!(parse_ffmpeg_duration(stderr), None);
+    }
+}
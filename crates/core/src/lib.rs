@@ -0,0 +1,4 @@
+pub mod config;
+pub mod domain;
+pub mod earnings_processor;
+pub mod stt;
@@ -0,0 +1,372 @@
+use super::{SpeechToText, SttError, TranscribeOptions, TranscriptChunk, TranscriptStream};
+use crate::earnings_processor::transcode;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio_stream::StreamExt;
+
+pub const DEFAULT_WINDOW_SECS: f32 = 30.0;
+pub const DEFAULT_OVERLAP_SECS: f32 = 2.0;
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How close two words' start times must be, in seconds, to be considered the
+/// same word repeated across an overlap region.
+const OVERLAP_MATCH_TOLERANCE_SECS: f32 = 0.5;
+
+/// Wraps any `SpeechToText` strategy to transcribe long input in fixed,
+/// overlapping windows transcribed concurrently, then stitches the windows back
+/// into one ordered stream. Useful for earnings calls that run well past what a
+/// single streaming session handles comfortably.
+pub struct ChunkedTranscriber {
+    inner: Arc<dyn SpeechToText>,
+}
+
+impl ChunkedTranscriber {
+    pub fn new(inner: Arc<dyn SpeechToText>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl SpeechToText for ChunkedTranscriber {
+    /// Splits `audio_path` into `options.chunk_window_secs`-long windows
+    /// (overlapping by `options.chunk_overlap_secs`), transcribes up to
+    /// `options.chunk_concurrency` of them at once, then merges the results back
+    /// into one ordered stream — offsetting each window's timestamps by its
+    /// start and dropping words duplicated in the overlap between adjacent
+    /// windows in favor of the higher-confidence copy.
+    async fn transcribe(
+        &self,
+        audio_path: &str,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptStream, SttError> {
+        raise_fd_limit();
+
+        let window_secs = options.chunk_window_secs.max(1.0);
+        let overlap_secs = options.chunk_overlap_secs.max(0.0).min(window_secs / 2.0);
+        let concurrency = options.chunk_concurrency.max(1);
+
+        let total_secs = transcode::probe_duration_secs(audio_path).await? as f32;
+        let windows = plan_windows(total_secs, window_secs, overlap_secs);
+
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = Vec::with_capacity(windows.len());
+
+        for (window_start, window_len) in windows {
+            let inner = Arc::clone(&self.inner);
+            let semaphore = Arc::clone(&semaphore);
+            let audio_path = audio_path.to_string();
+            let options = options.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+
+                let slice = transcode::extract_window(&audio_path, window_start, window_len)
+                    .await
+                    .map_err(SttError::Preprocessing)?;
+                let slice_path = slice.path.to_string_lossy().into_owned();
+
+                let mut stream = inner.transcribe(&slice_path, options).await?;
+                let mut chunks = Vec::new();
+                while let Some(item) = stream.next().await {
+                    let mut chunk = item?;
+                    offset_chunk(&mut chunk, window_start);
+                    chunks.push(chunk);
+                }
+
+                Ok::<Vec<TranscriptChunk>, SttError>(chunks)
+            }));
+        }
+
+        let mut all_chunks = Vec::new();
+        for task in tasks {
+            let chunks = task
+                .await
+                .map_err(|e| SttError::WorkerPanicked(e.to_string()))??;
+            all_chunks.extend(chunks);
+        }
+
+        all_chunks.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap());
+        dedup_overlapping_words(&mut all_chunks);
+
+        Ok(Box::pin(tokio_stream::iter(all_chunks.into_iter().map(Ok))))
+    }
+}
+
+/// Lays out `[start, len)` windows covering `[0, total_secs)`, each overlapping
+/// the next by `overlap_secs`.
+fn plan_windows(total_secs: f32, window_secs: f32, overlap_secs: f32) -> Vec<(f32, f32)> {
+    if total_secs <= 0.0 {
+        return vec![(0.0, 0.0)];
+    }
+
+    let stride = window_secs - overlap_secs;
+    let mut windows = Vec::new();
+    let mut start = 0.0f32;
+
+    loop {
+        let len = window_secs.min(total_secs - start);
+        windows.push((start, len));
+
+        if start + len >= total_secs {
+            break;
+        }
+        start += stride;
+    }
+
+    windows
+}
+
+fn offset_chunk(chunk: &mut TranscriptChunk, offset_secs: f32) {
+    chunk.start_time += offset_secs;
+    chunk.end_time += offset_secs;
+    for word in &mut chunk.words {
+        word.start_time += offset_secs;
+        word.end_time += offset_secs;
+    }
+}
+
+/// Drops words that are repeated across two chunks from adjacent, overlapping
+/// windows — recognized by a near-equal start time and matching text — keeping
+/// whichever copy has the higher confidence.
+fn dedup_overlapping_words(chunks: &mut [TranscriptChunk]) {
+    for i in 1..chunks.len() {
+        let (before, after) = chunks.split_at_mut(i);
+        let prev = before.last_mut().unwrap();
+        let cur = &mut after[0];
+
+        if cur.start_time > prev.end_time {
+            continue; // no overlap between these two chunks
+        }
+
+        cur.words.retain_mut(|word| {
+            let Some(dupe) = prev.words.iter_mut().find(|w| {
+                (w.start_time - word.start_time).abs() < OVERLAP_MATCH_TOLERANCE_SECS
+                    && w.text.trim().eq_ignore_ascii_case(word.text.trim())
+            }) else {
+                return true;
+            };
+
+            if word.confidence > dupe.confidence {
+                dupe.text.clear(); // superseded by the later copy; drop it below
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    for chunk in chunks.iter_mut() {
+        chunk.words.retain(|w| !w.text.is_empty());
+    }
+}
+
+#[cfg(unix)]
+fn raise_fd_limit() {
+    use libc::{getrlimit, rlimit, setrlimit, RLIMIT_NOFILE};
+
+    // Each concurrent worker opens its own socket/channel to the STT backend,
+    // so highly parallel runs can otherwise hit "too many open files".
+    unsafe {
+        let mut limit = rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        if getrlimit(RLIMIT_NOFILE, &mut limit) != 0 {
+            return;
+        }
+        if limit.rlim_cur >= limit.rlim_max {
+            return;
+        }
+        limit.rlim_cur = limit.rlim_max;
+        setrlimit(RLIMIT_NOFILE, &limit);
+    }
+}
+
+#[cfg(not(unix))]
+fn raise_fd_limit() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::Word;
+    use std::sync::Mutex;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_plan_windows_even_split() {
+        let windows = plan_windows(90.0, 30.0, 2.0);
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[0], (0.0, 30.0));
+        assert_eq!(windows[1].0, 28.0);
+        assert_eq!(windows.last().unwrap().0 + windows.last().unwrap().1, 90.0);
+    }
+
+    #[test]
+    fn test_plan_windows_shorter_than_one_window() {
+        let windows = plan_windows(10.0, 30.0, 2.0);
+        assert_eq!(windows, vec![(0.0, 10.0)]);
+    }
+
+    fn word(start_time: f32, text: &str, confidence: f32) -> Word {
+        Word {
+            start_time,
+            end_time: start_time + 0.4,
+            text: text.to_string(),
+            confidence,
+            stability: 1.0,
+        }
+    }
+
+    fn chunk(start_time: f32, end_time: f32, words: Vec<Word>) -> TranscriptChunk {
+        TranscriptChunk {
+            start_time,
+            end_time,
+            text: words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            speaker_id: String::new(),
+            confidence: 1.0,
+            words,
+            is_partial: false,
+        }
+    }
+
+    #[test]
+    fn test_offset_chunk_shifts_chunk_and_word_timestamps() {
+        let mut c = chunk(1.0, 2.0, vec![word(1.0, "hello", 0.9)]);
+
+        offset_chunk(&mut c, 28.0);
+
+        assert_eq!(c.start_time, 29.0);
+        assert_eq!(c.end_time, 30.0);
+        assert_eq!(c.words[0].start_time, 29.0);
+        assert_eq!(c.words[0].end_time, 29.4);
+    }
+
+    #[test]
+    fn test_dedup_overlapping_words_keeps_higher_confidence_copy() {
+        let mut chunks = vec![
+            chunk(0.0, 30.0, vec![word(28.1, "quarter", 0.6)]),
+            chunk(28.0, 58.0, vec![word(28.15, "quarter", 0.95)]),
+        ];
+
+        dedup_overlapping_words(&mut chunks);
+
+        assert!(chunks[0].words.is_empty());
+        assert_eq!(chunks[1].words.len(), 1);
+        assert_eq!(chunks[1].words[0].confidence, 0.95);
+    }
+
+    #[test]
+    fn test_dedup_overlapping_words_ignores_non_overlapping_chunks() {
+        let mut chunks = vec![
+            chunk(0.0, 10.0, vec![word(5.0, "revenue", 0.9)]),
+            chunk(20.0, 30.0, vec![word(25.0, "revenue", 0.9)]),
+        ];
+
+        dedup_overlapping_words(&mut chunks);
+
+        assert_eq!(chunks[0].words.len(), 1);
+        assert_eq!(chunks[1].words.len(), 1);
+    }
+
+    /// A fake `SpeechToText` that always returns one fixed chunk, recording every
+    /// `audio_path` it's called with so tests can assert on how the windows were
+    /// sliced up.
+    struct FakeStt {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FakeStt {
+        fn new() -> Self {
+            Self {
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SpeechToText for FakeStt {
+        async fn transcribe(
+            &self,
+            audio_path: &str,
+            _options: TranscribeOptions,
+        ) -> Result<TranscriptStream, SttError> {
+            self.calls.lock().unwrap().push(audio_path.to_string());
+            let chunks = vec![chunk(0.0, 1.0, vec![word(0.0, "window", 0.8)])];
+            Ok(Box::pin(tokio_stream::iter(chunks.into_iter().map(Ok))))
+        }
+    }
+
+    /// Writes `duration_secs` of 16-bit PCM silence as a WAV file, long enough for
+    /// `probe_duration_secs`/`extract_window` (both shell out to ffmpeg) to slice
+    /// into real windows.
+    fn write_silence_wav(file: &mut NamedTempFile, sample_rate: u32, duration_secs: f32) {
+        use std::io::Write;
+
+        let num_samples = (sample_rate as f32 * duration_secs) as usize;
+        let data = vec![0u8; num_samples * 2]; // 16-bit mono
+
+        let mut fmt_chunk = Vec::new();
+        fmt_chunk.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        fmt_chunk.extend_from_slice(&1u16.to_le_bytes()); // mono
+        fmt_chunk.extend_from_slice(&sample_rate.to_le_bytes());
+        fmt_chunk.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        fmt_chunk.extend_from_slice(&2u16.to_le_bytes());
+        fmt_chunk.extend_from_slice(&16u16.to_le_bytes());
+
+        let riff_size = 4 + (8 + fmt_chunk.len()) + (8 + data.len());
+
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&(riff_size as u32).to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&(fmt_chunk.len() as u32).to_le_bytes())
+            .unwrap();
+        file.write_all(&fmt_chunk).unwrap();
+        file.write_all(b"data").unwrap();
+        file.write_all(&(data.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&data).unwrap();
+        file.flush().unwrap();
+    }
+
+    /// Exercises the full `transcribe()` pipeline — window planning, concurrent
+    /// per-window transcription via a fake backend, timestamp offsetting and
+    /// overlap dedup — end to end, via a short real WAV file split into two
+    /// overlapping windows. Requires `ffmpeg` on `PATH`, same as the feature itself.
+    #[tokio::test]
+    async fn test_transcribe_merges_windows_into_one_ordered_stream() {
+        let mut file = NamedTempFile::new().unwrap();
+        write_silence_wav(&mut file, 16_000, 3.0);
+        let path = file.path().to_str().unwrap().to_string();
+
+        let inner = Arc::new(FakeStt::new());
+        let transcriber = ChunkedTranscriber::new(inner.clone());
+
+        let options = TranscribeOptions {
+            chunk_window_secs: 2.0,
+            chunk_overlap_secs: 0.5,
+            chunk_concurrency: 2,
+            ..TranscribeOptions::default()
+        };
+
+        let mut stream = transcriber.transcribe(&path, options).await.unwrap();
+        let mut chunks = Vec::new();
+        while let Some(item) = stream.next().await {
+            chunks.push(item.unwrap());
+        }
+
+        // plan_windows(3.0, 2.0, 0.5) lays out (0.0, 2.0) and (1.5, 1.5); each
+        // window's single fake chunk should come back offset by its window start.
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].start_time, 0.0);
+        assert_eq!(chunks[1].start_time, 1.5);
+        assert_eq!(inner.calls.lock().unwrap().len(), 2);
+    }
+}
@@ -17,4 +17,22 @@ pub enum SttError {
     #[error("Audio file not found: {0}")]
     #[diagnostic(help("Check that the file path is correct and the file exists"))]
     AudioFileNotFound(String),
+
+    #[error("Missing cloud STT API key")]
+    #[diagnostic(help(
+        "Set the VETTA_CLOUD_STT_API_KEY environment variable to your cloud transcription API key"
+    ))]
+    MissingApiKey,
+
+    #[error("Audio preprocessing failed: {0}")]
+    #[diagnostic(code(vetta::stt::preprocessing_failed))]
+    Preprocessing(#[from] crate::earnings_processor::IngestError),
+
+    #[error("A chunked transcription worker panicked: {0}")]
+    #[diagnostic(code(vetta::stt::worker_panicked))]
+    WorkerPanicked(String),
+
+    #[error("Failed to read audio while uploading: {0}")]
+    #[diagnostic(code(vetta::stt::upload_io))]
+    Io(#[from] std::io::Error),
 }
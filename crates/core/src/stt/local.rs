@@ -155,6 +155,7 @@ impl SpeechToText for LocalSttStrategy {
                     text: chunk.text,
                     speaker_id: chunk.speaker_id,
                     confidence: chunk.confidence,
+                    is_partial: false,
                     words: chunk
                         .words
                         .into_iter()
@@ -163,6 +164,9 @@ impl SpeechToText for LocalSttStrategy {
                             end_time: w.end_time,
                             text: w.text,
                             confidence: w.confidence,
+                            // Local chunks are always final (`is_partial: false`
+                            // above), so every word is as stable as it'll get.
+                            stability: 1.0,
                         })
                         .collect(),
                 })
@@ -1,13 +1,27 @@
+pub mod chunked;
 mod error;
 pub mod local;
+pub mod remote;
 
+pub use chunked::ChunkedTranscriber;
 pub use error::SttError;
 pub use local::LocalSttStrategy;
+pub use remote::RemoteSttStrategy;
 
 use async_trait::async_trait;
 use std::pin::Pin;
 use tokio_stream::Stream;
 
+/// Which `SpeechToText` implementation to construct for a transcription run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SttBackend {
+    /// The whisper sidecar, reached over a local Unix socket.
+    #[default]
+    Local,
+    /// A cloud transcription service, reached over a streaming gRPC connection.
+    Remote,
+}
+
 #[derive(Debug, Clone)]
 pub struct TranscriptChunk {
     pub start_time: f32,
@@ -16,6 +30,9 @@ pub struct TranscriptChunk {
     pub speaker_id: String,
     pub confidence: f32,
     pub words: Vec<Word>,
+    /// `true` while the segment may still be revised by the backend (e.g. a cloud
+    /// service's interim result). Local transcription only ever emits final chunks.
+    pub is_partial: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -24,14 +41,39 @@ pub struct Word {
     pub end_time: f32,
     pub text: String,
     pub confidence: f32,
+    /// How likely this word is to still change in a later, more-refined result.
+    /// Only meaningful for interim (`is_partial`) chunks from a streaming backend;
+    /// local transcription only ever emits final words, so it reports `1.0`.
+    pub stability: f32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct TranscribeOptions {
     pub language: Option<String>,
     pub initial_prompt: Option<String>,
     pub diarization: bool,
     pub num_speakers: u32,
+    /// Window size, in seconds, `ChunkedTranscriber` splits long input into.
+    pub chunk_window_secs: f32,
+    /// Overlap, in seconds, between adjacent windows, used to de-duplicate words
+    /// that land on both sides of a split.
+    pub chunk_overlap_secs: f32,
+    /// How many windows `ChunkedTranscriber` transcribes concurrently.
+    pub chunk_concurrency: usize,
+}
+
+impl Default for TranscribeOptions {
+    fn default() -> Self {
+        Self {
+            language: None,
+            initial_prompt: None,
+            diarization: false,
+            num_speakers: 0,
+            chunk_window_secs: chunked::DEFAULT_WINDOW_SECS,
+            chunk_overlap_secs: chunked::DEFAULT_OVERLAP_SECS,
+            chunk_concurrency: chunked::DEFAULT_CONCURRENCY,
+        }
+    }
 }
 
 pub type TranscriptStream = Pin<Box<dyn Stream<Item = Result<TranscriptChunk, SttError>> + Send>>;
@@ -0,0 +1,222 @@
+use super::{SpeechToText, SttError, TranscribeOptions, TranscriptChunk, TranscriptStream, Word};
+use crate::earnings_processor::{self, transcode::TranscodedAudio};
+use async_trait::async_trait;
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use tonic::transport::{Channel, Endpoint};
+use tonic::Request;
+
+pub mod proto {
+    tonic::include_proto!("cloud_speech");
+}
+
+use proto::{
+    cloud_speech_to_text_client::CloudSpeechToTextClient, AudioPacket, StreamingConfig,
+    TranscriptEvent,
+};
+
+/// Audio is uploaded to the cloud service in fixed-size PCM packets as it is read
+/// from disk, rather than all at once, so the server can start transcribing before
+/// the whole file has been uploaded.
+const UPLOAD_PACKET_BYTES: usize = 8 * 1024;
+const UPLOAD_SAMPLE_RATE_HZ: i32 = 16_000;
+/// How many outbound packets can be buffered ahead of the gRPC stream before the
+/// upload task blocks on a slow connection.
+const UPLOAD_CHANNEL_CAPACITY: usize = 8;
+
+pub struct RemoteSttStrategy {
+    endpoint: String,
+    api_key: String,
+}
+
+impl RemoteSttStrategy {
+    /// Create a `RemoteSttStrategy` for a cloud transcription service at `endpoint`,
+    /// authenticating requests with `api_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SttError::MissingApiKey` if `api_key` is empty.
+    pub async fn connect(
+        endpoint: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Result<Self, SttError> {
+        let api_key = api_key.into();
+        if api_key.is_empty() {
+            return Err(SttError::MissingApiKey);
+        }
+
+        Ok(Self {
+            endpoint: endpoint.into(),
+            api_key,
+        })
+    }
+
+    async fn client(&self) -> Result<CloudSpeechToTextClient<Channel>, SttError> {
+        let channel = Endpoint::from_shared(self.endpoint.clone())
+            .map_err(tonic::transport::Error::from)?
+            .connect()
+            .await?;
+
+        Ok(CloudSpeechToTextClient::new(channel))
+    }
+}
+
+#[async_trait]
+impl SpeechToText for RemoteSttStrategy {
+    /// Streams `audio_path` to the cloud transcription service over a long-lived
+    /// duplex connection and maps its partial/final result events into
+    /// `TranscriptChunk`s, tagging interim results with `is_partial` so the caller
+    /// can redraw a live progress line instead of appending a new one each time.
+    ///
+    /// The cloud service expects raw 16-bit PCM at `UPLOAD_SAMPLE_RATE_HZ`, not a
+    /// container, so `audio_path` is always run through
+    /// `earnings_processor::transcode` first regardless of `Config::normalize_audio`
+    /// — this path has no other way to guarantee the bytes it claims are PCM.
+    async fn transcribe(
+        &self,
+        audio_path: &str,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptStream, SttError> {
+        if !std::path::Path::new(audio_path).exists() {
+            return Err(SttError::AudioFileNotFound(audio_path.to_string()));
+        }
+
+        let mut client = self.client().await?;
+        let api_key = self.api_key.clone();
+
+        let normalized = earnings_processor::transcode::normalize_to_wav(audio_path)
+            .await
+            .map_err(SttError::Preprocessing)?;
+        let (pcm_offset, pcm_len) = earnings_processor::wav_pcm_span(&normalized.path)
+            .map_err(SttError::Preprocessing)?;
+
+        let config = StreamingConfig {
+            language: options.language.unwrap_or_default(),
+            initial_prompt: options.initial_prompt.unwrap_or_default(),
+            diarization: options.diarization,
+            num_speakers: options.num_speakers as i32,
+        };
+
+        // The outbound gRPC stream can only carry `AudioPacket`s, not `Result`s, so
+        // upload errors can't be yielded into it directly. Run the upload on its own
+        // task instead and report any failure back through `upload_done`, appended
+        // to the inbound transcript stream below instead of being silently dropped.
+        let (packet_tx, packet_rx) = mpsc::channel(UPLOAD_CHANNEL_CAPACITY);
+        let (upload_done_tx, upload_done_rx) = oneshot::channel();
+        tokio::spawn(upload_pcm(
+            normalized,
+            pcm_offset,
+            pcm_len,
+            config,
+            packet_tx,
+            upload_done_tx,
+        ));
+
+        let mut request = Request::new(ReceiverStream::new(packet_rx));
+        request
+            .metadata_mut()
+            .insert("x-api-key", api_key.parse().map_err(|_| SttError::MissingApiKey)?);
+
+        let mut inbound = client.streaming_transcribe(request).await?.into_inner();
+
+        let combined = async_stream::stream! {
+            while let Some(result) = inbound.next().await {
+                yield result.map_err(SttError::Service).map(map_event);
+            }
+            if let Ok(Err(upload_err)) = upload_done_rx.await {
+                yield Err(upload_err);
+            }
+        };
+
+        Ok(Box::pin(combined))
+    }
+}
+
+/// Reads the PCM payload of `normalized` (the `data` chunk spanning
+/// `[pcm_offset, pcm_offset + pcm_len)`) and sends it to `packet_tx` in
+/// `UPLOAD_PACKET_BYTES`-sized packets, preceded by a config-only packet. Reports
+/// the outcome on `done_tx` rather than panicking or failing silently.
+///
+/// `normalized` is held for the duration of the upload so its backing temp file
+/// isn't deleted out from under us, and is cleaned up as soon as we're done with it.
+async fn upload_pcm(
+    normalized: TranscodedAudio,
+    pcm_offset: u64,
+    pcm_len: u32,
+    config: StreamingConfig,
+    packet_tx: mpsc::Sender<AudioPacket>,
+    done_tx: oneshot::Sender<Result<(), SttError>>,
+) {
+    let result = upload_pcm_inner(&normalized.path, pcm_offset, pcm_len, &config, &packet_tx).await;
+    let _ = done_tx.send(result);
+}
+
+async fn upload_pcm_inner(
+    path: &Path,
+    pcm_offset: u64,
+    pcm_len: u32,
+    config: &StreamingConfig,
+    packet_tx: &mpsc::Sender<AudioPacket>,
+) -> Result<(), SttError> {
+    let config_packet = AudioPacket {
+        pcm_data: Vec::new(),
+        sample_rate_hz: UPLOAD_SAMPLE_RATE_HZ,
+        config: Some(config.clone()),
+    };
+    if packet_tx.send(config_packet).await.is_err() {
+        return Ok(()); // receiver dropped; the call ended before we got going
+    }
+
+    let mut file = File::open(path).await.map_err(SttError::Io)?;
+    file.seek(std::io::SeekFrom::Start(pcm_offset))
+        .await
+        .map_err(SttError::Io)?;
+
+    let mut remaining = pcm_len as usize;
+    let mut buf = vec![0u8; UPLOAD_PACKET_BYTES];
+    while remaining > 0 {
+        let want = remaining.min(buf.len());
+        let n = file.read(&mut buf[..want]).await.map_err(SttError::Io)?;
+        if n == 0 {
+            break;
+        }
+        remaining -= n;
+
+        let packet = AudioPacket {
+            pcm_data: buf[..n].to_vec(),
+            sample_rate_hz: UPLOAD_SAMPLE_RATE_HZ,
+            config: None,
+        };
+        if packet_tx.send(packet).await.is_err() {
+            break; // receiver dropped; server is done listening
+        }
+    }
+
+    Ok(())
+}
+
+fn map_event(event: TranscriptEvent) -> TranscriptChunk {
+    TranscriptChunk {
+        start_time: event.start_time,
+        end_time: event.end_time,
+        text: event.text,
+        speaker_id: event.speaker_id,
+        confidence: event.confidence,
+        is_partial: !event.is_final,
+        words: event
+            .words
+            .into_iter()
+            .map(|w| Word {
+                start_time: w.start_time,
+                end_time: w.end_time,
+                text: w.text,
+                confidence: w.confidence,
+                stability: w.stability,
+            })
+            .collect(),
+    }
+}